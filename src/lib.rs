@@ -11,6 +11,8 @@
 //! 1. A server side application where you use Rust to generate the HTML and you send the generated string as the request result
 //! 2. A static site generator where you create a blog template which takes a set of arguments and returns a html representation
 //! 3. A frontend generator for applications making use of html, such as [Tauri](https://tauri.app/) and [Electron](https://www.electronjs.org/)
+use std::collections::{HashMap, HashSet};
+
 enum Closing { TAG, NONE }
 impl Closing {
     fn clone(&self) -> Closing {
@@ -22,7 +24,9 @@ impl Closing {
 }
 enum Children {
     Text(String),
-    Node(Node)
+    Node(Node),
+    /// Markup rendered verbatim, with no escaping. Set via `Node::inner_html`.
+    Raw(String)
 }
 /// A mutable struct composed of a tag, attribute list, child list and closing type.
 /// 
@@ -30,14 +34,14 @@ enum Children {
 /// ```
 /// Node {
 ///     tag: String,
-///     attributes: Option<Vec<String>>,
+///     attributes: Option<Vec<(String, String)>>,
 ///     children: Option<Vec<Children>>,
 ///     closing_type: Closing
 /// }
 /// ```
 pub struct Node {
     tag: String,
-    attributes: Option<Vec<String>>,
+    attributes: Option<Vec<(String, String)>>,
     children: Option<Vec<Children>>,
     closing_type: Closing
 }
@@ -67,14 +71,21 @@ impl Node {
     /// ```
     pub fn set_attribute(&mut self, name: &str, value: &str) {
         match &mut self.attributes {
-            Some(attributes) => attributes.push(format!("{}=\"{}\"", name, value)),
+            Some(attributes) => attributes.push((name.to_string(), value.to_string())),
             None => {
-                let mut attributes: Vec<String> = Vec::new();
-                attributes.push(format!("{}=\"{}\"", name, value));
+                let mut attributes: Vec<(String, String)> = Vec::new();
+                attributes.push((name.to_string(), value.to_string()));
                 self.attributes = Some(attributes);
             },
         }
     }
+
+    /// Returns the value of the Node's attribute, or `None` if it isn't set. If the
+    /// same attribute was set more than once (e.g. via `set_attribute_list`), the
+    /// first value wins, matching `set_attribute`'s append order.
+    fn get_attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.as_ref()?.iter().find(|(attr_name, _)| attr_name == name).map(|(_, value)| value.as_str())
+    }
     /// Sets the values of the Node's attributes. This does not return an error if the wrong (key, value) was set.
     /// 
     /// # Example
@@ -133,6 +144,39 @@ impl Node {
             }
         }
     }
+    /// Sets raw markup as a Node's content, rendered verbatim instead of escaped
+    /// like `inner_text`. Only use this with markup you trust: anything passed
+    /// here is emitted as-is, `<` and `&` included.
+    ///
+    /// # Example
+    /// ```
+    /// use aurochs::Document;
+    ///
+    /// let mut paragraph = Document::create_element("p");
+    /// paragraph.inner_html("Hello <b>World</b>!");
+    /// ```
+    ///
+    /// # Javascript Equivalent
+    /// [MDN web docs](https://developer.mozilla.org/en-US/docs/Web/API/Element/innerHTML)
+    /// ```
+    /// let paragraph = document.createElement("p");
+    /// paragraph.innerHTML = "Hello <b>World</b>!";
+    /// ```
+    ///
+    /// # HTML
+    /// ```
+    /// <p>Hello <b>World</b>!</p>
+    /// ```
+    pub fn inner_html(&mut self, raw: &str) {
+        match &mut self.children {
+            Some(child) => child.push(Children::Raw(raw.to_string())),
+            None => {
+                let mut children: Vec<Children> = Vec::new();
+                children.push(Children::Raw(raw.to_string()));
+                self.children = Some(children);
+            }
+        }
+    }
     /// Adds a Node to the end of the list of children of a specified parent Node.
     /// 
     /// # Example
@@ -270,6 +314,7 @@ impl Node {
                     cloned_children.push(match child {
                         Children::Text(text) => Children::Text(text.clone()),
                         Children::Node(node) => Children::Node(node.clone_node()),
+                        Children::Raw(raw) => Children::Raw(raw.clone()),
                     });
                 }
                 Some(cloned_children)
@@ -316,30 +361,972 @@ impl Node {
     /// </html>
     /// ```
     pub fn render(&self) -> String {
+        let children = self.render_children();
+
+        // A tag-less Node is a Fragment (see `Document::create_fragment`), which
+        // `Document::parse` also falls back to for multi-root input; it renders
+        // as just its children, with no wrapping tag.
+        if self.tag.is_empty() {
+            return children;
+        }
+
         let attributes = match &self.attributes {
-            Some(attrs) => format!(" {}", attrs.join(" ")),
+            Some(attrs) => {
+                let rendered: Vec<String> = attrs.iter().map(|(name, value)| format!("{}=\"{}\"", name, escape_attribute(value))).collect();
+                format!(" {}", rendered.join(" "))
+            },
             None => String::new(),
         };
 
-        let children = match &self.children {
-            Some(children) => {
-                let mut children_html = String::new();
-                for child in children {
-                    match child {
-                        Children::Text(text) => children_html.push_str(text),
-                        Children::Node(node) => children_html.push_str(&node.render()),
+        match &self.closing_type {
+            Closing::TAG => format!("<{}{}>{}</{}>", self.tag, attributes, children, self.tag),
+            Closing::NONE => format!("<{}{}>", self.tag, attributes),
+        }
+    }
+
+    /// Renders this Node's children, escaping text and passing `Children::Raw`
+    /// through verbatim. Adjacent `Children::Text` entries are joined into one
+    /// string before escaping rather than escaped one at a time.
+    fn render_children(&self) -> String {
+        let Some(children) = &self.children else {
+            return String::new();
+        };
+
+        let mut html = String::new();
+        let mut children = children.iter().peekable();
+
+        while let Some(child) = children.next() {
+            match child {
+                Children::Text(text) => {
+                    let mut combined = text.clone();
+                    while let Some(Children::Text(next)) = children.peek() {
+                        combined.push_str(next);
+                        children.next();
                     }
-                }
-                children_html
+                    html.push_str(&escape_text(&combined));
+                },
+                Children::Node(node) => html.push_str(&node.render()),
+                Children::Raw(raw) => html.push_str(raw),
+            }
+        }
+
+        html
+    }
+
+    /// Returns the parsed Node tree as a human-readable string: each nested
+    /// element on its own line, indented four spaces per level of depth. A
+    /// node whose children are all text (no nested elements) stays on one
+    /// line, the same as `render` would produce it.
+    ///
+    /// # Example
+    /// ```
+    /// use aurochs::Document;
+    ///
+    /// let mut html = Document::create_element("html");
+    /// let mut head = Document::create_element("head");
+    /// let mut body = Document::create_element("body");
+    /// html.append_child_list(vec![ head, body ]);
+    ///
+    /// println!("{}", html.render_pretty());
+    /// ```
+    ///
+    /// # HTML
+    /// ```
+    /// <html>
+    ///     <head></head>
+    ///     <body></body>
+    /// </html>
+    /// ```
+    pub fn render_pretty(&self) -> String {
+        self.render_pretty_at(0)
+    }
+
+    fn render_pretty_at(&self, depth: usize) -> String {
+        let indent = "    ".repeat(depth);
+
+        if self.tag.is_empty() {
+            return self.render_children_pretty(depth).join("\n");
+        }
+
+        let attributes = match &self.attributes {
+            Some(attrs) => {
+                let rendered: Vec<String> = attrs.iter().map(|(name, value)| format!("{}=\"{}\"", name, escape_attribute(value))).collect();
+                format!(" {}", rendered.join(" "))
             },
             None => String::new(),
         };
 
-        match &self.closing_type {
-            Closing::TAG => format!("<{}{}>{}</{}>", self.tag, attributes, children, self.tag),
-            Closing::NONE => format!("<{}{}>", self.tag, attributes),
+        if matches!(self.closing_type, Closing::NONE) {
+            return format!("{}<{}{}>", indent, self.tag, attributes);
+        }
+
+        match &self.children {
+            Some(children) if children.iter().any(|child| matches!(child, Children::Node(_))) => {
+                let inner = self.render_children_pretty(depth + 1).join("\n");
+                format!("{}<{}{}>\n{}\n{}</{}>", indent, self.tag, attributes, inner, indent, self.tag)
+            },
+            _ => format!("{}<{}{}>{}</{}>", indent, self.tag, attributes, self.render_children(), self.tag),
+        }
+    }
+
+    /// Renders each child on its own line at the given depth, recursing into
+    /// nested elements so their own children are indented one level further.
+    fn render_children_pretty(&self, depth: usize) -> Vec<String> {
+        let Some(children) = &self.children else {
+            return Vec::new();
+        };
+
+        let indent = "    ".repeat(depth);
+        let mut lines = Vec::new();
+        let mut children = children.iter().peekable();
+
+        while let Some(child) = children.next() {
+            match child {
+                Children::Text(text) => {
+                    let mut combined = text.clone();
+                    while let Some(Children::Text(next)) = children.peek() {
+                        combined.push_str(next);
+                        children.next();
+                    }
+                    lines.push(format!("{}{}", indent, escape_text(&combined)));
+                },
+                Children::Raw(raw) => lines.push(format!("{}{}", indent, raw)),
+                Children::Node(node) => lines.push(node.render_pretty_at(depth)),
+            }
+        }
+
+        lines
+    }
+
+    /// Renders this Node preceded by a leading `<!DOCTYPE html>`, for a Node
+    /// that represents a full document (such as one from
+    /// `Document::create_default`), so the caller doesn't have to prepend the
+    /// doctype by hand.
+    ///
+    /// # Example
+    /// ```
+    /// use aurochs::Document;
+    ///
+    /// let page = Document::create_default();
+    /// println!("{}", page.render_document());
+    /// ```
+    pub fn render_document(&self) -> String {
+        format!("<!DOCTYPE html>{}", self.render())
+    }
+
+    /// Pre-renders this node's children into a single cached string and
+    /// replaces them with it, so a later `render` just splices that string
+    /// back in instead of walking and formatting every descendant again.
+    /// Meant for static (inert) subtrees instantiated many times, e.g. the
+    /// same header or footer emitted across thousands of pages in a static
+    /// site generator.
+    ///
+    /// Freezing erases the child `Node`s in favor of their rendered text, so
+    /// there's nothing left underneath to reach with `query_selector_mut` or
+    /// mutate directly; further content can still be appended after the
+    /// cached portion, it just won't itself be cached.
+    ///
+    /// # Example
+    /// ```
+    /// use aurochs::Document;
+    ///
+    /// let mut footer = Document::create_element("footer");
+    /// footer.inner_text("(c) Aurochs");
+    /// footer.freeze();
+    /// assert_eq!(footer.render(), "<footer>(c) Aurochs</footer>");
+    /// ```
+    pub fn freeze(&mut self) {
+        if self.children.is_none() {
+            return;
+        }
+        let cached = self.render_children();
+        self.children = Some(vec![Children::Raw(cached)]);
+    }
+
+    /// Returns the first descendant (or self) matching a CSS-like selector, or
+    /// `None` if nothing matches. See `query_selector_all` for the supported
+    /// selector subset.
+    ///
+    /// # Example
+    /// ```
+    /// use aurochs::Document;
+    ///
+    /// let html = Document::parse(r#"<div><p id="content">Hi</p></div>"#);
+    /// let content = html.query_selector("#content").unwrap();
+    /// ```
+    ///
+    /// # Javascript Equivalent
+    /// [MDN web docs](https://developer.mozilla.org/en-US/docs/Web/API/Element/querySelector)
+    /// ```
+    /// const content = html.querySelector("#content");
+    /// ```
+    pub fn query_selector(&self, sel: &str) -> Option<&Node> {
+        self.query_selector_all(sel).into_iter().next()
+    }
+
+    /// Returns every descendant (or self) matching a CSS-like selector.
+    ///
+    /// Supports tag names, `#id`, `.class`, attribute presence `[name]` and
+    /// equality `[name="value"]`, any number of which may be chained onto a
+    /// single simple selector (e.g. `div#content.card[data-x="1"]`), and the
+    /// descendant combinator (whitespace) between simple selectors.
+    ///
+    /// # Example
+    /// ```
+    /// use aurochs::Document;
+    ///
+    /// let html = Document::parse(r#"<ul><li class="item">A</li><li class="item">B</li></ul>"#);
+    /// let items = html.query_selector_all("ul .item");
+    /// assert_eq!(items.len(), 2);
+    /// ```
+    ///
+    /// # Javascript Equivalent
+    /// [MDN web docs](https://developer.mozilla.org/en-US/docs/Web/API/Element/querySelectorAll)
+    /// ```
+    /// const items = html.querySelectorAll("ul .item");
+    /// ```
+    pub fn query_selector_all(&self, sel: &str) -> Vec<&Node> {
+        let selectors = parse_selector(sel);
+        let mut results = Vec::new();
+        let mut ancestors = Vec::new();
+        collect_matches(self, &selectors, &mut ancestors, &mut results);
+        results
+    }
+
+    /// Like `query_selector`, but returns a mutable reference so the matched
+    /// descendant (or self) can be appended to or otherwise edited in place,
+    /// e.g. reaching into the `head`/`body` of `Document::create_default`.
+    ///
+    /// # Example
+    /// ```
+    /// use aurochs::Document;
+    ///
+    /// let mut page = Document::create_default();
+    /// if let Some(body) = page.query_selector_mut("body") {
+    ///     body.append_child(Document::create_element("p"));
+    /// }
+    /// ```
+    pub fn query_selector_mut(&mut self, sel: &str) -> Option<&mut Node> {
+        let selectors = parse_selector(sel);
+        let mut ancestors = Vec::new();
+        let mut path = Vec::new();
+        if !find_path(self, &selectors, &mut ancestors, &mut path) {
+            return None;
+        }
+
+        let mut node = self;
+        for index in path {
+            node = match node.children.as_mut()?.get_mut(index)? {
+                Children::Node(child) => child,
+                _ => return None,
+            };
+        }
+        Some(node)
+    }
+
+    /// Returns the first descendant (or self) with the given `id` attribute.
+    ///
+    /// # Javascript Equivalent
+    /// [MDN web docs](https://developer.mozilla.org/en-US/docs/Web/API/Document/getElementById)
+    pub fn get_element_by_id(&self, id: &str) -> Option<&Node> {
+        self.query_selector(&format!("#{}", id))
+    }
+
+    /// Returns every descendant (or self) carrying the given class.
+    ///
+    /// # Javascript Equivalent
+    /// [MDN web docs](https://developer.mozilla.org/en-US/docs/Web/API/Document/getElementsByClassName)
+    pub fn get_elements_by_class_name(&self, class_name: &str) -> Vec<&Node> {
+        self.query_selector_all(&format!(".{}", class_name))
+    }
+
+    /// Returns every descendant (or self) with the given tag name.
+    ///
+    /// # Javascript Equivalent
+    /// [MDN web docs](https://developer.mozilla.org/en-US/docs/Web/API/Document/getElementsByTagName)
+    pub fn get_elements_by_tag_name(&self, tag_name: &str) -> Vec<&Node> {
+        self.query_selector_all(tag_name)
+    }
+
+    /// Walks the tree and removes anything `policy` doesn't allow: a node whose
+    /// tag isn't in the allowlist is unwrapped (its children are hoisted into its
+    /// parent rather than dropped with it), a node whose tag is explicitly
+    /// dropped (see `SanitizePolicy::drop_tag`) is removed along with its
+    /// content, and attributes not allowlisted for a node's tag are stripped.
+    /// The check applies to `self` as well as its descendants, so sanitizing a
+    /// tree whose *root* is disallowed still strips it rather than leaving it
+    /// untouched. Useful for the "server renders HTML out of an untrusted
+    /// fragment" case.
+    ///
+    /// Markup stored as raw content (set with `inner_html`, or collapsed by
+    /// `freeze`) is not parsed here: it is kept verbatim and not checked
+    /// against `policy`.
+    ///
+    /// # Example
+    /// ```
+    /// use aurochs::{Document, SanitizePolicy};
+    ///
+    /// let mut html = Document::parse(r#"<p onclick="evil()">hi <script>evil()</script></p>"#);
+    /// html.sanitize(&SanitizePolicy::default());
+    /// assert_eq!(html.render(), "<p>hi </p>");
+    ///
+    /// let mut root_script = Document::parse("<script>evil()</script>");
+    /// root_script.sanitize(&SanitizePolicy::default());
+    /// assert_eq!(root_script.render(), "");
+    /// ```
+    pub fn sanitize(&mut self, policy: &SanitizePolicy) {
+        if !self.tag.is_empty() {
+            if policy.tag_dropped(&self.tag) {
+                *self = Document::create_fragment();
+                return;
+            }
+            if !policy.tag_allowed(&self.tag) {
+                // Unwrap: lose the tag and become a plain fragment, which
+                // renders as just its (sanitized) children, same as if a
+                // parent had hoisted them.
+                self.tag = String::new();
+                self.attributes = None;
+                self.closing_type = Closing::NONE;
+            }
+        }
+
+        self.strip_disallowed_attributes(policy);
+        self.sanitize_children(policy);
+    }
+
+    fn sanitize_children(&mut self, policy: &SanitizePolicy) {
+        let Some(children) = self.children.take() else {
+            return;
+        };
+
+        let mut sanitized = Vec::new();
+        for child in children {
+            match child {
+                Children::Text(text) => sanitized.push(Children::Text(text)),
+                Children::Raw(raw) => sanitized.push(Children::Raw(raw)),
+                Children::Node(mut node) => {
+                    node.sanitize(policy);
+                    sanitized.push(Children::Node(node));
+                },
+            }
+        }
+
+        self.children = if sanitized.is_empty() { None } else { Some(sanitized) };
+    }
+
+    fn strip_disallowed_attributes(&mut self, policy: &SanitizePolicy) {
+        let tag = self.tag.clone();
+        if let Some(attributes) = &mut self.attributes {
+            attributes.retain(|(name, _)| policy.attribute_allowed(&tag, name));
+        }
+        if matches!(&self.attributes, Some(attrs) if attrs.is_empty()) {
+            self.attributes = None;
+        }
+    }
+
+    /// Rewrites every `<img>` element's `src` attribute to `data-source`,
+    /// neutralizing remote image loading without removing the element or
+    /// disturbing layout. Handy for email/newsletter rendering where images
+    /// shouldn't fetch until explicitly allowed.
+    ///
+    /// # Example
+    /// ```
+    /// use aurochs::Document;
+    ///
+    /// let mut html = Document::parse(r#"<img src="https://example.com/x.png">"#);
+    /// html.neutralize_images();
+    /// assert_eq!(html.render(), r#"<img data-source="https://example.com/x.png">"#);
+    /// ```
+    pub fn neutralize_images(&mut self) {
+        if self.tag == "img" {
+            if let Some(attributes) = &mut self.attributes {
+                for (name, _) in attributes.iter_mut() {
+                    if name == "src" {
+                        "data-source".clone_into(name);
+                    }
+                }
+            }
+        }
+
+        if let Some(children) = &mut self.children {
+            for child in children {
+                if let Children::Node(node) = child {
+                    node.neutralize_images();
+                }
+            }
+        }
+    }
+
+    /// If this Node has exactly one child and that child is itself a Node,
+    /// returns that child in place of `self`; otherwise returns `self`
+    /// unchanged. Used by the `html!` macro so a root with a single element
+    /// returns that element directly instead of wrapping it in a Fragment.
+    #[doc(hidden)]
+    pub fn __html_macro_unwrap(mut self) -> Node {
+        match self.children.as_deref() {
+            Some([Children::Node(_)]) => match self.children.take().unwrap().pop() {
+                Some(Children::Node(node)) => node,
+                _ => unreachable!(),
+            },
+            _ => self,
+        }
+    }
+}
+
+/// An allowlist of tag names and, per tag, the attribute names permitted on it,
+/// used by `Node::sanitize` to strip untrusted markup down to a known-safe
+/// subset. Build one with `SanitizePolicy::new()` and `allow_tag`/
+/// `allow_attribute`, or start from `SanitizePolicy::default()`.
+pub struct SanitizePolicy {
+    allowed_tags: HashSet<String>,
+    dropped_tags: HashSet<String>,
+    allowed_attributes: HashMap<String, HashSet<String>>,
+}
+
+impl SanitizePolicy {
+    /// Returns an empty policy that allows nothing; build it up with
+    /// `allow_tag`, `allow_attribute` and `drop_tag`.
+    pub fn new() -> SanitizePolicy {
+        SanitizePolicy { allowed_tags: HashSet::new(), dropped_tags: HashSet::new(), allowed_attributes: HashMap::new() }
+    }
+
+    /// Allows a tag to remain in the tree during `sanitize`.
+    pub fn allow_tag(&mut self, tag: &str) {
+        self.allowed_tags.insert(tag.to_string());
+    }
+
+    /// Allows an attribute to remain on a given tag during `sanitize`.
+    pub fn allow_attribute(&mut self, tag: &str, attribute: &str) {
+        self.allowed_attributes.entry(tag.to_string()).or_insert_with(HashSet::new).insert(attribute.to_string());
+    }
+
+    /// Marks a tag whose entire subtree (including its text) must be removed
+    /// by `sanitize` rather than unwrapped, e.g. `script` and `style`, whose
+    /// content isn't safe to hoist into the parent as display text.
+    pub fn drop_tag(&mut self, tag: &str) {
+        self.dropped_tags.insert(tag.to_string());
+    }
+
+    fn tag_allowed(&self, tag: &str) -> bool {
+        self.allowed_tags.contains(tag)
+    }
+
+    fn tag_dropped(&self, tag: &str) -> bool {
+        self.dropped_tags.contains(tag)
+    }
+
+    fn attribute_allowed(&self, tag: &str, attribute: &str) -> bool {
+        self.allowed_attributes.get(tag).is_some_and(|attrs| attrs.contains(attribute))
+    }
+}
+
+impl Default for SanitizePolicy {
+    /// Common text/structural tags (headings, paragraphs, lists, tables, links,
+    /// images, inline formatting) with their ordinary attributes; `script` and
+    /// `style` are dropped outright, everything else unrecognized is unwrapped,
+    /// and no event-handler attributes (`onclick` and friends) are allowed.
+    fn default() -> Self {
+        let mut policy = SanitizePolicy::new();
+
+        for tag in [
+            "p", "span", "div", "a", "ul", "ol", "li", "br", "hr",
+            "b", "i", "u", "strong", "em", "small", "mark", "sub", "sup",
+            "h1", "h2", "h3", "h4", "h5", "h6", "blockquote", "code", "pre",
+            "img", "table", "thead", "tbody", "tr", "td", "th",
+        ] {
+            policy.allow_tag(tag);
+        }
+
+        for tag in [
+            "p", "span", "div", "a", "ul", "ol", "li", "blockquote", "code", "pre",
+            "img", "table", "thead", "tbody", "tr", "td", "th",
+        ] {
+            policy.allow_attribute(tag, "class");
+        }
+
+        policy.allow_attribute("a", "href");
+        policy.allow_attribute("a", "title");
+        policy.allow_attribute("img", "src");
+        policy.allow_attribute("img", "alt");
+
+        policy.drop_tag("script");
+        policy.drop_tag("style");
+
+        policy
+    }
+}
+
+/// A single compound selector between descendant combinators, e.g. the `div.card`
+/// in `section div.card`.
+struct SimpleSelector {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+impl SimpleSelector {
+    fn matches(&self, node: &Node) -> bool {
+        if let Some(tag) = &self.tag {
+            if &node.tag != tag {
+                return false;
+            }
+        }
+
+        if let Some(id) = &self.id {
+            if node.get_attribute("id") != Some(id.as_str()) {
+                return false;
+            }
+        }
+
+        if !self.classes.is_empty() {
+            let classes: Vec<&str> = node.get_attribute("class").map(|c| c.split_whitespace().collect()).unwrap_or_default();
+            if !self.classes.iter().all(|class| classes.contains(&class.as_str())) {
+                return false;
+            }
+        }
+
+        self.attributes.iter().all(|(name, expected)| match (node.get_attribute(name), expected) {
+            (Some(actual), Some(expected)) => actual == expected,
+            (Some(_), None) => true,
+            (None, _) => false,
+        })
+    }
+}
+
+/// Splits a selector string on the descendant combinator (whitespace) into the
+/// compound selectors that must match along an ancestor chain, in order.
+fn parse_selector(sel: &str) -> Vec<SimpleSelector> {
+    sel.split_whitespace().map(parse_simple_selector).collect()
+}
+
+/// Parses a single compound selector, e.g. `div#content.card[data-x="1"]`.
+fn parse_simple_selector(input: &str) -> SimpleSelector {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tag = None;
+    let mut id = None;
+    let mut classes = Vec::new();
+    let mut attributes = Vec::new();
+    let mut i = 0;
+
+    if i < chars.len() && !"#.[".contains(chars[i]) {
+        let start = i;
+        while i < chars.len() && !"#.[".contains(chars[i]) {
+            i += 1;
+        }
+        tag = Some(chars[start..i].iter().collect());
+    }
+
+    while i < chars.len() {
+        match chars[i] {
+            '#' => {
+                i += 1;
+                let start = i;
+                while i < chars.len() && !"#.[".contains(chars[i]) {
+                    i += 1;
+                }
+                id = Some(chars[start..i].iter().collect());
+            },
+            '.' => {
+                i += 1;
+                let start = i;
+                while i < chars.len() && !"#.[".contains(chars[i]) {
+                    i += 1;
+                }
+                classes.push(chars[start..i].iter().collect());
+            },
+            '[' => {
+                i += 1;
+                let start = i;
+                while i < chars.len() && chars[i] != ']' {
+                    i += 1;
+                }
+                let attr_src: String = chars[start..i].iter().collect();
+                i = (i + 1).min(chars.len());
+                attributes.push(parse_attribute_selector(&attr_src));
+            },
+            _ => i += 1,
+        }
+    }
+
+    SimpleSelector { tag, id, classes, attributes }
+}
+
+/// Parses the inside of an attribute selector, e.g. `name` or `name="value"`.
+fn parse_attribute_selector(input: &str) -> (String, Option<String>) {
+    match input.split_once('=') {
+        Some((name, value)) => (name.trim().to_string(), Some(value.trim().trim_matches('"').trim_matches('\'').to_string())),
+        None => (input.trim().to_string(), None),
+    }
+}
+
+/// Walks `node` and its descendants depth-first, tracking the chain of
+/// ancestors so the descendant combinator in `selectors` can be matched against
+/// any ancestor rather than only the immediate parent.
+fn collect_matches<'a>(node: &'a Node, selectors: &[SimpleSelector], ancestors: &mut Vec<&'a Node>, results: &mut Vec<&'a Node>) {
+    ancestors.push(node);
+
+    if matches_chain(ancestors, selectors) {
+        results.push(node);
+    }
+
+    if let Some(children) = &node.children {
+        for child in children {
+            if let Children::Node(child_node) = child {
+                collect_matches(child_node, selectors, ancestors, results);
+            }
+        }
+    }
+
+    ancestors.pop();
+}
+
+/// Checks whether the last selector matches the current node and each earlier
+/// selector matches some (not necessarily adjacent) earlier ancestor, in order.
+fn matches_chain(ancestors: &[&Node], selectors: &[SimpleSelector]) -> bool {
+    let Some((last_selector, earlier_selectors)) = selectors.split_last() else {
+        return false;
+    };
+
+    if !last_selector.matches(ancestors[ancestors.len() - 1]) {
+        return false;
+    }
+
+    let mut remaining_ancestors = &ancestors[..ancestors.len() - 1];
+    for selector in earlier_selectors.iter().rev() {
+        loop {
+            let Some((ancestor, rest)) = remaining_ancestors.split_last() else {
+                return false;
+            };
+            remaining_ancestors = rest;
+            if selector.matches(ancestor) {
+                break;
+            }
+        }
+    }
+
+    true
+}
+
+/// Depth-first search for the first node matching `selectors`, recording the
+/// child index taken at each level in `path` so the caller can re-descend via
+/// `&mut` afterwards (matching itself only needs shared references).
+fn find_path<'a>(node: &'a Node, selectors: &[SimpleSelector], ancestors: &mut Vec<&'a Node>, path: &mut Vec<usize>) -> bool {
+    ancestors.push(node);
+
+    if matches_chain(ancestors, selectors) {
+        ancestors.pop();
+        return true;
+    }
+
+    if let Some(children) = &node.children {
+        for (index, child) in children.iter().enumerate() {
+            if let Children::Node(child_node) = child {
+                path.push(index);
+                if find_path(child_node, selectors, ancestors, path) {
+                    ancestors.pop();
+                    return true;
+                }
+                path.pop();
+            }
+        }
+    }
+
+    ancestors.pop();
+    false
+}
+
+/// Escapes the characters that would otherwise break or inject markup in a
+/// text node: `&`, `<` and `>`. `&` is escaped first so the entities produced
+/// for the others aren't themselves re-escaped.
+fn escape_text(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Like `escape_text`, plus `"`, since attribute values are rendered inside a
+/// double-quoted `name="value"` pair.
+fn escape_attribute(value: &str) -> String {
+    escape_text(value).replace('"', "&quot;")
+}
+
+/// Tag names that `Document::create_element` assigns `Closing::NONE`, i.e. that
+/// never expect a matching end tag. Kept in sync with `Document::create_element`.
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "command", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+];
+
+/// A single lexical unit produced while scanning an HTML string, consumed by the
+/// tree-builder in `Document::parse`.
+enum Token {
+    StartTag { name: String, attributes: Vec<(String, String)>, self_closing: bool },
+    EndTag { name: String },
+    Text(String),
+}
+
+/// Splits an HTML string into a flat stream of start tags, end tags and text runs.
+/// Comments and the doctype declaration are recognized and discarded.
+fn tokenize(html: &str) -> Vec<Token> {
+    let chars: Vec<char> = html.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut text = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] != '<' {
+            text.push(chars[i]);
+            i += 1;
+            continue;
+        }
+
+        if !text.is_empty() {
+            tokens.push(Token::Text(decode_entities(&std::mem::take(&mut text))));
+        }
+
+        let Some(end) = find_tag_end(&chars, i + 1) else {
+            // Unterminated tag: treat the rest of the input as text and stop.
+            text.extend(&chars[i..]);
+            break;
+        };
+
+        let inner: String = chars[i + 1..end].iter().collect();
+        i = end + 1;
+
+        if inner.starts_with('!') {
+            continue; // doctype or comment, not part of the tree
+        }
+
+        if let Some(name) = inner.strip_prefix('/') {
+            tokens.push(Token::EndTag { name: name.trim().to_lowercase() });
+            continue;
+        }
+
+        let (body, self_closing) = match inner.strip_suffix('/') {
+            Some(rest) => (rest.trim_end(), true),
+            None => (inner.as_str(), false),
+        };
+
+        let mut parts = body.splitn(2, char::is_whitespace);
+        let name = parts.next().unwrap_or("").trim().to_lowercase();
+        let attributes = parse_attributes(parts.next().unwrap_or("").trim());
+
+        tokens.push(Token::StartTag { name, attributes, self_closing });
+    }
+
+    if !text.is_empty() {
+        tokens.push(Token::Text(decode_entities(&text)));
+    }
+
+    tokens
+}
+
+/// Finds the `>` that closes the tag starting at `start` (the character after
+/// the opening `<`), skipping over any `>` that falls inside a `"`- or
+/// `'`-quoted attribute value so quoted content like `title="a>b"` doesn't
+/// truncate the tag early.
+fn find_tag_end(chars: &[char], start: usize) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    let mut i = start;
+
+    while i < chars.len() {
+        match quote {
+            Some(q) if chars[i] == q => quote = None,
+            Some(_) => {},
+            None => match chars[i] {
+                '"' | '\'' => quote = Some(chars[i]),
+                '>' => return Some(i),
+                _ => {},
+            },
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Decodes the character references real-world HTML relies on: the named
+/// `&amp;`, `&lt;`, `&gt;`, `&quot;` and `&apos;`, plus numeric `&#NNN;` and
+/// `&#xHHH;` references. Anything else starting with `&` is left as-is, since
+/// it's either plain text or an entity this parser doesn't recognize.
+///
+/// Applied to parsed text and attribute values so `render`'s own escaping
+/// (chunk0-4) doesn't double-escape content that round-tripped through
+/// `Document::parse`.
+fn decode_entities(input: &str) -> String {
+    if !input.contains('&') {
+        return input.to_string();
+    }
+
+    let chars: Vec<char> = input.chars().collect();
+    let mut output = String::with_capacity(input.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] != '&' {
+            output.push(chars[i]);
+            i += 1;
+            continue;
+        }
+
+        let Some(end) = chars[i + 1..].iter().position(|&c| c == ';').map(|pos| i + 1 + pos) else {
+            output.push(chars[i]);
+            i += 1;
+            continue;
+        };
+
+        let entity: String = chars[i + 1..end].iter().collect();
+        let decoded = match entity.as_str() {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                if let Some(hex) = entity.strip_prefix('#').and_then(|rest| rest.strip_prefix(['x', 'X'])) {
+                    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                } else if let Some(dec) = entity.strip_prefix('#') {
+                    dec.parse::<u32>().ok().and_then(char::from_u32)
+                } else {
+                    None
+                }
+            },
+        };
+
+        match decoded {
+            Some(c) => {
+                output.push(c);
+                i = end + 1;
+            },
+            None => {
+                // Not a recognized entity; keep the `&` literal and re-scan
+                // from the next character rather than consuming up to `;`.
+                output.push('&');
+                i += 1;
+            },
+        }
+    }
+
+    output
+}
+
+/// Parses a tag's attribute source (everything after the tag name) into
+/// `(name, value)` pairs, understanding `name`, `name="value"` and `name='value'`.
+fn parse_attributes(input: &str) -> Vec<(String, String)> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut attributes = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+        let name_start = i;
+        while i < chars.len() && chars[i] != '=' && !chars[i].is_whitespace() {
+            i += 1;
+        }
+        let name: String = chars[name_start..i].iter().collect();
+        if name.is_empty() {
+            break;
+        }
+
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+
+        if i < chars.len() && chars[i] == '=' {
+            i += 1;
+            while i < chars.len() && chars[i].is_whitespace() {
+                i += 1;
+            }
+            match chars.get(i) {
+                Some(&quote) if quote == '"' || quote == '\'' => {
+                    i += 1;
+                    let value_start = i;
+                    while i < chars.len() && chars[i] != quote {
+                        i += 1;
+                    }
+                    let value: String = chars[value_start..i].iter().collect();
+                    attributes.push((name, decode_entities(&value)));
+                    i += 1; // skip the closing quote
+                },
+                _ => {
+                    let value_start = i;
+                    while i < chars.len() && !chars[i].is_whitespace() {
+                        i += 1;
+                    }
+                    let value: String = chars[value_start..i].iter().collect();
+                    attributes.push((name, decode_entities(&value)));
+                },
+            }
+        } else {
+            attributes.push((name, String::new()));
         }
     }
+
+    attributes
+}
+
+/// Consumes a token stream and builds the top-level `Node`s it describes, using a
+/// stack of open elements: each start tag pushes a new `Node`, each end tag pops
+/// the matching one and appends it to whatever is now on top of the stack.
+fn build_tree(tokens: Vec<Token>) -> Vec<Node> {
+    let mut stack: Vec<Node> = Vec::new();
+    let mut roots: Vec<Node> = Vec::new();
+
+    for token in tokens {
+        match token {
+            Token::Text(text) => {
+                match stack.last_mut() {
+                    Some(top) => top.inner_text(&text),
+                    // Stray top-level text (not inside any open element):
+                    // carry it as its own tag-less fragment so it ends up
+                    // among `roots` instead of being dropped.
+                    None => {
+                        let mut fragment = Document::create_fragment();
+                        fragment.inner_text(&text);
+                        roots.push(fragment);
+                    },
+                }
+            },
+            Token::StartTag { name, attributes, self_closing } => {
+                let mut node = Document::create_element(&name);
+                for (attr_name, attr_value) in attributes {
+                    node.set_attribute(&attr_name, &attr_value);
+                }
+
+                let is_void = VOID_ELEMENTS.contains(&name.as_str());
+                if is_void || self_closing {
+                    match stack.last_mut() {
+                        Some(top) => top.append_child(node),
+                        None => roots.push(node),
+                    }
+                } else {
+                    stack.push(node);
+                }
+            },
+            Token::EndTag { name } => {
+                if matches!(stack.last(), Some(top) if top.tag == name) {
+                    let node = stack.pop().unwrap();
+                    match stack.last_mut() {
+                        Some(parent) => parent.append_child(node),
+                        None => roots.push(node),
+                    }
+                }
+                // A mismatched end tag is ignored rather than rejected, the same
+                // leniency real HTML parsers apply to malformed markup.
+            },
+        }
+    }
+
+    // Anything still open when the input ends (unterminated tags) gets attached
+    // to its parent so the tree isn't silently truncated.
+    while let Some(node) = stack.pop() {
+        match stack.last_mut() {
+            Some(parent) => parent.append_child(node),
+            None => roots.push(node),
+        }
+    }
+
+    roots
 }
 /// The root Element of the HTML Tree
 /// 
@@ -374,10 +1361,236 @@ impl Document {
         };
         Node { tag: element_tag.to_string(), attributes: None, children: None, closing_type }
     }
-    // pub fn create_default() -> Vec<Node> {
-    // TODO: create a default template and append the content to it
-    // return a vec![ HEAD, BODY ] so we can append elements to it
-    // }
+    /// Parses an HTML string into the same `Node`/`Children` tree `create_element`
+    /// and `append_child` build by hand, so a document can be round-tripped:
+    /// parse it, mutate the tree, then `render` it back out.
+    ///
+    /// Void elements (see `create_element`) are parsed without requiring a
+    /// matching end tag, and runs of text between tags are coalesced into a
+    /// single `Children::Text` entry. If the input has more than one top-level
+    /// element, a tag-less synthetic root is returned that renders as just the
+    /// concatenation of its children.
+    ///
+    /// Character references (`&amp;`, `&lt;`, `&#39;`, `&#x27;`, ...) in text
+    /// and attribute values are decoded as they're parsed, so `render`'s own
+    /// escaping doesn't double up on a parse/render round trip.
+    ///
+    /// # Example
+    /// ```
+    /// use aurochs::Document;
+    ///
+    /// let html = Document::parse(r#"<p class="intro">Hello <b>World</b>!</p>"#);
+    /// println!("{}", html.render());
+    /// ```
+    ///
+    /// # Javascript Equivalent
+    /// [MDN web docs](https://developer.mozilla.org/en-US/docs/Web/API/DOMParser)
+    /// ```
+    /// const html = new DOMParser().parseFromString(source, "text/html");
+    /// ```
+    pub fn parse(html: &str) -> Node {
+        let mut roots = build_tree(tokenize(html));
+
+        if roots.len() == 1 {
+            return roots.remove(0);
+        }
+
+        let mut root = Document::create_fragment();
+        for node in roots {
+            root.append_child(node);
+        }
+        root
+    }
+
+    /// Returns a Fragment: a tag-less `Node` that renders as just the
+    /// concatenation of its children, with no wrapping tag of its own. Lets a
+    /// template function return several sibling elements (or none) from a
+    /// single `Node`, the same way a document fragment lets reactive
+    /// frameworks return multiple roots from a component.
+    ///
+    /// # Example
+    /// ```
+    /// use aurochs::Document;
+    ///
+    /// let mut fragment = Document::create_fragment();
+    /// fragment.append_child(Document::create_element("h1"));
+    /// fragment.append_child(Document::create_element("p"));
+    /// ```
+    pub fn create_fragment() -> Node {
+        Node { tag: String::new(), attributes: None, children: None, closing_type: Closing::NONE }
+    }
+
+    /// Returns a ready-to-serve `<html><head></head><body></body></html>`
+    /// skeleton, so callers don't have to assemble the same boilerplate for
+    /// every page. Reach `head` or `body` with `query_selector_mut` to append
+    /// content, and render the result with `render_document` to include the
+    /// leading doctype.
+    ///
+    /// # Example
+    /// ```
+    /// use aurochs::Document;
+    ///
+    /// let mut page = Document::create_default();
+    /// page.query_selector_mut("body").unwrap().append_child(Document::create_element("p"));
+    /// println!("{}", page.render_document());
+    /// ```
+    ///
+    /// # HTML
+    /// ```
+    /// <!DOCTYPE html><html><head></head><body><p></p></body></html>
+    /// ```
+    pub fn create_default() -> Node {
+        let mut html = Document::create_element("html");
+        html.append_child(Document::create_element("head"));
+        html.append_child(Document::create_element("body"));
+        html
+    }
 
     // TODO: create custom element -> <x-custom></x-custom>
+}
+
+/// Declaratively builds a `Node` tree, lowering to the same
+/// `create_element`/`set_attribute`/`inner_text`/`append_child` calls used when
+/// building a tree by hand. The root may hold any number of sibling elements:
+/// with exactly one it's returned directly, otherwise (zero or several) it's
+/// wrapped in a `Document::create_fragment`.
+///
+/// # Example
+/// ```
+/// use aurochs::html;
+///
+/// let card = html! {
+///     div[class="card"] {
+///         p { "Hello" }
+///         img[src="x.png"]
+///     }
+/// };
+/// println!("{}", card.render());
+/// ```
+#[macro_export]
+macro_rules! html {
+    ($($tokens:tt)*) => {{
+        let mut __aurochs_root = $crate::Document::create_fragment();
+        $crate::__html_children!(__aurochs_root; $($tokens)*);
+        __aurochs_root.__html_macro_unwrap()
+    }};
+}
+
+/// Implementation detail of `html!`: a tt-muncher that appends each item in
+/// `$($tokens)*` onto `$parent` (a string literal becomes `inner_text`, an
+/// `ident[attrs]{children}`/`ident[attrs]`/`ident{children}`/`ident` becomes an
+/// element appended via `append_child`), recursing until no tokens remain.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __html_children {
+    ($parent:ident;) => {};
+
+    ($parent:ident; $text:literal $($rest:tt)*) => {{
+        $parent.inner_text($text);
+        $crate::__html_children!($parent; $($rest)*);
+    }};
+
+    ($parent:ident; $tag:ident [ $($attrs:tt)* ] { $($children:tt)* } $($rest:tt)*) => {{
+        let mut __aurochs_node = $crate::Document::create_element(stringify!($tag));
+        $crate::__html_attrs!(__aurochs_node; $($attrs)*);
+        $crate::__html_children!(__aurochs_node; $($children)*);
+        $parent.append_child(__aurochs_node);
+        $crate::__html_children!($parent; $($rest)*);
+    }};
+
+    ($parent:ident; $tag:ident [ $($attrs:tt)* ] $($rest:tt)*) => {{
+        let mut __aurochs_node = $crate::Document::create_element(stringify!($tag));
+        $crate::__html_attrs!(__aurochs_node; $($attrs)*);
+        $parent.append_child(__aurochs_node);
+        $crate::__html_children!($parent; $($rest)*);
+    }};
+
+    ($parent:ident; $tag:ident { $($children:tt)* } $($rest:tt)*) => {{
+        let mut __aurochs_node = $crate::Document::create_element(stringify!($tag));
+        $crate::__html_children!(__aurochs_node; $($children)*);
+        $parent.append_child(__aurochs_node);
+        $crate::__html_children!($parent; $($rest)*);
+    }};
+
+    ($parent:ident; $tag:ident $($rest:tt)*) => {{
+        let __aurochs_node = $crate::Document::create_element(stringify!($tag));
+        $parent.append_child(__aurochs_node);
+        $crate::__html_children!($parent; $($rest)*);
+    }};
+}
+
+/// Implementation detail of `html!`: a tt-muncher over `name="value"` pairs
+/// inside a tag's `[...]`, calling `set_attribute` for each.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __html_attrs {
+    ($node:ident;) => {};
+
+    ($node:ident; $name:ident = $value:literal) => {{
+        $node.set_attribute(stringify!($name), $value);
+    }};
+
+    ($node:ident; $name:ident = $value:literal, $($rest:tt)*) => {{
+        $node.set_attribute(stringify!($name), $value);
+        $crate::__html_attrs!($node; $($rest)*);
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_drops_disallowed_root() {
+        let mut html = Document::parse(r#"<script>evil()</script>"#);
+        html.sanitize(&SanitizePolicy::default());
+        assert_eq!(html.render(), "");
+    }
+
+    #[test]
+    fn sanitize_unwraps_unrecognized_root() {
+        let mut html = Document::parse(r#"<custom-tag class="x">hi <b>bold</b></custom-tag>"#);
+        html.sanitize(&SanitizePolicy::default());
+        assert_eq!(html.render(), "hi <b>bold</b>");
+    }
+
+    #[test]
+    fn sanitize_keeps_allowed_root_and_strips_nested_script() {
+        let mut html = Document::parse(r#"<p onclick="evil()">hi <script>evil()</script></p>"#);
+        html.sanitize(&SanitizePolicy::default());
+        assert_eq!(html.render(), "<p>hi </p>");
+    }
+
+    #[test]
+    fn parse_decodes_entities_and_render_does_not_double_escape() {
+        let html = Document::parse(r#"<p title="Tom &amp; Jerry">Tom &amp; Jerry</p>"#);
+        assert_eq!(html.render(), r#"<p title="Tom &amp; Jerry">Tom &amp; Jerry</p>"#);
+    }
+
+    #[test]
+    fn parse_wraps_multiple_roots_in_a_fragment() {
+        let html = Document::parse("<p>one</p><p>two</p>");
+        assert_eq!(html.render(), "<p>one</p><p>two</p>");
+    }
+
+    #[test]
+    fn query_selector_finds_nested_element_by_tag() {
+        let html = Document::parse(r#"<div><p class="intro">Hello</p></div>"#);
+        let found = html.query_selector("p").expect("should find <p>");
+        assert_eq!(found.render(), r#"<p class="intro">Hello</p>"#);
+    }
+
+    #[test]
+    fn query_selector_matches_class_and_attribute_selectors() {
+        let html = Document::parse(r#"<div><p class="intro" data-id="1">Hello</p></div>"#);
+        assert!(html.query_selector(".intro").is_some());
+        assert!(html.query_selector("[data-id]").is_some());
+        assert!(html.query_selector(".missing").is_none());
+    }
+
+    #[test]
+    fn query_selector_all_collects_every_match() {
+        let html = Document::parse("<ul><li>a</li><li>b</li><li>c</li></ul>");
+        assert_eq!(html.query_selector_all("li").len(), 3);
+    }
 }
\ No newline at end of file